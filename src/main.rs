@@ -2,48 +2,427 @@ pub mod command {
     // https://www.dofactory.com/net/command-design-pattern
     pub trait Command {
         fn execute(&mut self);
+        fn undo(&mut self);
+    }
+
+    pub struct MacroCommand {
+        commands: Vec<Box<dyn Command>>
+    }
+
+    impl MacroCommand {
+        pub fn new(commands: Vec<Box<dyn Command>>) -> Self {
+            Self { commands }
+        }
+    }
+
+    impl Command for MacroCommand {
+        fn execute(&mut self) {
+            for command in self.commands.iter_mut() {
+                command.execute();
+            }
+        }
+
+        fn undo(&mut self) {
+            for command in self.commands.iter_mut().rev() {
+                command.undo();
+            }
+        }
+    }
+
+    pub struct CommandInvoker {
+        undo_stack: Vec<Box<dyn Command>>,
+        redo_stack: Vec<Box<dyn Command>>
+    }
+
+    impl CommandInvoker {
+        pub fn new() -> Self {
+            Self {
+                undo_stack: vec![],
+                redo_stack: vec![]
+            }
+        }
+
+        pub fn execute(&mut self, mut command: Box<dyn Command>) {
+            command.execute();
+            self.undo_stack.push(command);
+            self.redo_stack.clear();
+        }
+
+        pub fn undo(&mut self) -> bool {
+            match self.undo_stack.pop() {
+                Some(mut command) => {
+                    command.undo();
+                    self.redo_stack.push(command);
+                    true
+                },
+                None => false
+            }
+        }
+
+        pub fn redo(&mut self) -> bool {
+            match self.redo_stack.pop() {
+                Some(mut command) => {
+                    command.execute();
+                    self.undo_stack.push(command);
+                    true
+                },
+                None => false
+            }
+        }
+    }
+
+    // Stored as FnMut, not FnOnce, since execute only borrows self mutably and may run more than once.
+    pub struct FnCommand<F: FnMut()> {
+        action: F
+    }
+
+    impl<F: FnMut()> FnCommand<F> {
+        pub fn new(action: F) -> Self {
+            Self { action }
+        }
+    }
+
+    impl<F: FnMut()> Command for FnCommand<F> {
+        fn execute(&mut self) {
+            (self.action)();
+        }
+
+        fn undo(&mut self) {
+            // Closures have no inherent inverse action, so there's nothing to undo.
+        }
+    }
+
+    impl dyn Command {
+        pub fn from_fn<F: FnMut() + 'static>(action: F) -> Box<dyn Command> {
+            Box::new(FnCommand::new(action))
+        }
     }
 }
 
 pub mod observer {
     // https://www.dofactory.com/net/observer-design-pattern
+    #[derive(Clone, Copy)]
+    pub enum TriggerEvent {
+        OnChange,
+        OnAttach,
+        OnDetach
+    }
+
+    pub struct Trigger<T> {
+        pub event: TriggerEvent,
+        pub subject_name: String,
+        pub previous_value: Option<T>,
+        pub current_value: Option<T>
+    }
+
     pub trait Observer<T> {
-        fn on_subject_updated(&mut self, update_source: &T);
+        fn on_trigger(&mut self, trigger: &Trigger<T>);
+
+        // A Subject may sweep out observers that report false (e.g. backed by a dropped Weak handle).
+        fn is_alive(&self) -> bool {
+            true
+        }
     }
 
     pub trait Subject<T> {
         fn attach_observer(&mut self, observer_key: &String, observer: Box<dyn Observer<T>>);
         fn detach_observer(&mut self, observer_key: &String) -> bool;
-        fn notify_observers(&mut self);
+        fn notify_observers(&mut self, event: TriggerEvent, previous_value: Option<T>);
+    }
+
+    enum SyncMessage<T> {
+        Notify { event: TriggerEvent, previous_value: Option<T>, current_value: T },
+        Shutdown
+    }
+
+    type SyncObserverMap<T> = std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, Box<dyn Observer<T> + Send>>>>;
+
+    // notify_async hands the updated state to a background worker over a channel instead of
+    // running observers on the caller's thread, so observers must be Send.
+    pub struct SyncSubject<T: Send + Clone + 'static> {
+        name: String,
+        observers: SyncObserverMap<T>,
+        sender: Option<std::sync::mpsc::Sender<SyncMessage<T>>>,
+        worker: Option<std::thread::JoinHandle<()>>
+    }
+
+    impl<T: Send + Clone + 'static> SyncSubject<T> {
+        pub fn new(name: String) -> Self {
+            let observers: SyncObserverMap<T> =
+                std::sync::Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+            let (sender, receiver) = std::sync::mpsc::channel::<SyncMessage<T>>();
+            let worker_observers = observers.clone();
+            let worker_name = name.clone();
+            let worker = std::thread::spawn(move || {
+                for message in receiver {
+                    match message {
+                        SyncMessage::Notify { event, previous_value, current_value } => {
+                            let mut observers = worker_observers.lock().unwrap();
+                            for observer in observers.values_mut() {
+                                observer.on_trigger(&Trigger {
+                                    event,
+                                    subject_name: worker_name.clone(),
+                                    previous_value: previous_value.clone(),
+                                    current_value: Some(current_value.clone())
+                                });
+                            }
+                        },
+                        SyncMessage::Shutdown => break
+                    }
+                }
+            });
+            Self {
+                name,
+                observers,
+                sender: Some(sender),
+                worker: Some(worker)
+            }
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn attach_observer(&mut self, observer_key: &String, observer: Box<dyn Observer<T> + Send>) {
+            self.observers.lock().unwrap().insert(observer_key.to_string(), observer);
+        }
+
+        pub fn detach_observer(&mut self, observer_key: &String) -> bool {
+            self.observers.lock().unwrap().remove(observer_key).is_some()
+        }
+
+        pub fn notify_async(&self, previous_value: Option<T>, current_value: T) {
+            if let Some(sender) = &self.sender {
+                let _ = sender.send(SyncMessage::Notify { event: TriggerEvent::OnChange, previous_value, current_value });
+            }
+        }
+
+        // Blocks until the worker thread has drained everything already sent and exited.
+        pub fn shutdown(&mut self) {
+            if let Some(sender) = self.sender.take() {
+                let _ = sender.send(SyncMessage::Shutdown);
+            }
+            if let Some(worker) = self.worker.take() {
+                let _ = worker.join();
+            }
+        }
+    }
+
+    impl<T: Send + Clone + 'static> Drop for SyncSubject<T> {
+        fn drop(&mut self) {
+            self.shutdown();
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::sync::{Arc, Mutex};
+
+        struct RecordingObserver {
+            received: Arc<Mutex<Vec<bool>>>
+        }
+
+        impl Observer<bool> for RecordingObserver {
+            fn on_trigger(&mut self, trigger: &Trigger<bool>) {
+                if let TriggerEvent::OnChange = trigger.event {
+                    self.received.lock().unwrap().push(trigger.current_value.unwrap());
+                }
+            }
+        }
+
+        #[test]
+        fn notify_async_delivers_in_order_before_shutdown_returns() {
+            let mut subject = SyncSubject::<bool>::new("test".to_string());
+            let received = Arc::new(Mutex::new(vec![]));
+            subject.attach_observer(&"recorder".to_string(), Box::new(RecordingObserver { received: received.clone() }));
+
+            subject.notify_async(Some(false), true);
+            subject.notify_async(Some(true), false);
+            subject.shutdown(); // blocks until the worker has drained both notifications
+
+            assert_eq!(*received.lock().unwrap(), vec![true, false]);
+        }
     }
 }
 
 pub mod mediator {
-    use std::{any::{TypeId, Any}, collections::HashMap};
+    use std::{any::{Any, TypeId}, cell::RefCell, collections::{HashMap, HashSet}, rc::Rc};
 
     // https://www.dofactory.com/net/mediator-design-pattern
     pub trait Mediator {
         fn mediate(&mut self, handler: Box<dyn Handler>);
         fn broadcast(&mut self, event_type: TypeId, event: Box<dyn Any>);
+        fn request(&mut self, event_type: TypeId, event: Box<dyn Any>) -> Vec<Box<dyn Any>>;
     }
 
     pub trait Handler {
         fn handle_event(&mut self, event: &Box<dyn Any>);
+        fn handle_request(&mut self, event: &Box<dyn Any>) -> Option<Box<dyn Any>>;
         fn handles_type(&self) -> TypeId;
     }
 
+    pub trait Codec {
+        fn encode(&self, payload: &[u8]) -> Vec<u8>;
+        fn decode(&self, payload: &[u8]) -> Vec<u8>;
+    }
+
+    pub struct IdentityCodec;
+
+    impl Codec for IdentityCodec {
+        fn encode(&self, payload: &[u8]) -> Vec<u8> {
+            payload.to_vec()
+        }
+
+        fn decode(&self, payload: &[u8]) -> Vec<u8> {
+            payload.to_vec()
+        }
+    }
+
+    // topic() is an associated fn, not a method, so a mediator can subscribe to it before
+    // it has ever seen an instance of E.
+    pub trait TopicEvent: Sized {
+        fn topic() -> &'static str;
+        fn to_bytes(&self) -> Vec<u8>;
+        fn from_bytes(payload: &[u8]) -> Option<Self>;
+    }
+
+    pub trait Transport {
+        fn publish(&mut self, topic: &str, payload: &[u8]);
+        // Opts a topic into poll's results; a transport may otherwise keep publishing to it
+        // without ever handing payloads back.
+        fn subscribe(&mut self, topic: &str);
+        // Removes and decodes every payload published to a subscribed topic since the last poll.
+        fn poll(&mut self) -> Vec<(String, Vec<u8>)>;
+    }
+
+    // In-memory Transport for tests and local demos; an MQTT-style client would implement
+    // the same trait instead.
+    pub struct LoopbackTransport {
+        queues: HashMap<String, Vec<Vec<u8>>>,
+        subscribed_topics: HashSet<String>,
+        codec: Box<dyn Codec>
+    }
+
+    impl LoopbackTransport {
+        pub fn new(codec: Box<dyn Codec>) -> Self {
+            Self {
+                queues: HashMap::new(),
+                subscribed_topics: HashSet::new(),
+                codec
+            }
+        }
+
+        pub fn drain(&mut self, topic: &str) -> Vec<Vec<u8>> {
+            self.queues.get_mut(topic)
+                .map(std::mem::take)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|payload| self.codec.decode(&payload))
+                .collect()
+        }
+    }
+
+    impl Transport for LoopbackTransport {
+        fn publish(&mut self, topic: &str, payload: &[u8]) {
+            let encoded = self.codec.encode(payload);
+            self.queues.entry(topic.to_string()).or_default().push(encoded);
+        }
+
+        fn subscribe(&mut self, topic: &str) {
+            self.subscribed_topics.insert(topic.to_string());
+        }
+
+        fn poll(&mut self) -> Vec<(String, Vec<u8>)> {
+            self.subscribed_topics.iter()
+                .filter_map(|topic| self.queues.get_mut(topic).map(|queue| (topic.clone(), std::mem::take(queue))))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|(topic, queue)| {
+                    let codec = &self.codec;
+                    queue.into_iter().map(move |payload| (topic.clone(), codec.decode(&payload))).collect::<Vec<_>>()
+                })
+                .collect()
+        }
+    }
+
+    impl Transport for Rc<RefCell<LoopbackTransport>> {
+        fn publish(&mut self, topic: &str, payload: &[u8]) {
+            self.borrow_mut().publish(topic, payload);
+        }
+
+        fn subscribe(&mut self, topic: &str) {
+            self.borrow_mut().subscribe(topic);
+        }
+
+        fn poll(&mut self) -> Vec<(String, Vec<u8>)> {
+            self.borrow_mut().poll()
+        }
+    }
+
+    type TopicDecoder = Box<dyn Fn(&[u8]) -> Option<Box<dyn Any>>>;
+
     pub struct ConcreteMediator {
-        handlers: HashMap<TypeId, Vec<Box<dyn Handler>>>
+        handlers: HashMap<TypeId, Vec<Box<dyn Handler>>>,
+        transport: Option<Box<dyn Transport>>,
+        subscriptions: HashMap<String, (TypeId, TopicDecoder)>
     }
-    
+
     impl ConcreteMediator {
         pub fn new() -> Self {
             Self {
-                handlers: HashMap::new()
+                handlers: HashMap::new(),
+                transport: None,
+                subscriptions: HashMap::new()
+            }
+        }
+
+        pub fn set_transport(&mut self, transport: Box<dyn Transport>) {
+            self.transport = Some(transport);
+        }
+
+        pub fn subscribe_remote<E: Any + TopicEvent>(&mut self, event_type: TypeId) {
+            if let Some(transport) = &mut self.transport {
+                transport.subscribe(E::topic());
+            }
+            let decode: TopicDecoder = Box::new(|payload| {
+                E::from_bytes(payload).map(|event| Box::new(event) as Box<dyn Any>)
+            });
+            self.subscriptions.insert(E::topic().to_string(), (event_type, decode));
+        }
+
+        pub fn poll_transport(&mut self) {
+            let received = match &mut self.transport {
+                Some(transport) => transport.poll(),
+                None => return
+            };
+            for (topic, payload) in received {
+                if let Some((event_type, decode)) = self.subscriptions.get(&topic) {
+                    if let Some(event) = decode(&payload) {
+                        self.broadcast(*event_type, event);
+                    }
+                }
+            }
+        }
+
+        pub fn broadcast_remote<E: Any + TopicEvent>(&mut self, event_type: TypeId, event: E) {
+            if let Some(transport) = &mut self.transport {
+                transport.publish(E::topic(), &event.to_bytes());
+            }
+            self.broadcast(event_type, Box::new(event));
+        }
+
+        pub fn receive<E: Any + TopicEvent>(&mut self, event_type: TypeId, payload: &[u8]) -> bool {
+            match E::from_bytes(payload) {
+                Some(event) => {
+                    self.broadcast(event_type, Box::new(event));
+                    true
+                },
+                None => false
             }
         }
     }
-    
+
     impl Mediator for ConcreteMediator {
         fn mediate(&mut self, handler: Box<dyn Handler>) {
             let handler_map_value: &mut Vec<Box<dyn Handler>>;
@@ -69,6 +448,381 @@ pub mod mediator {
                 None => {}
             }
         }
+
+        fn request(&mut self, event_type: TypeId, event: Box<dyn Any>) -> Vec<Box<dyn Any>> {
+            match self.handlers.get_mut(&event_type) {
+                Some(handlers) => {
+                    let mut responses = vec![];
+                    for handler in handlers {
+                        if let Some(response) = handler.handle_request(&event) {
+                            responses.push(response);
+                        }
+                    }
+                    responses
+                },
+                None => vec![]
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        struct PingEvent {
+            count: u8
+        }
+
+        impl TopicEvent for PingEvent {
+            fn topic() -> &'static str {
+                "ping"
+            }
+
+            fn to_bytes(&self) -> Vec<u8> {
+                vec![self.count]
+            }
+
+            fn from_bytes(payload: &[u8]) -> Option<Self> {
+                payload.first().map(|&count| PingEvent { count })
+            }
+        }
+
+        struct PingHandler {
+            received: Rc<RefCell<Vec<u8>>>
+        }
+
+        impl Handler for PingHandler {
+            fn handle_event(&mut self, event: &Box<dyn Any>) {
+                if let Some(ping) = event.downcast_ref::<PingEvent>() {
+                    self.received.borrow_mut().push(ping.count);
+                }
+            }
+
+            fn handle_request(&mut self, _event: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+                None
+            }
+
+            fn handles_type(&self) -> TypeId {
+                TypeId::of::<PingEvent>()
+            }
+        }
+
+        #[test]
+        fn loopback_transport_poll_only_returns_subscribed_topics() {
+            let mut transport = LoopbackTransport::new(Box::new(IdentityCodec));
+            transport.publish("other", &[9]);
+            assert!(transport.poll().is_empty(), "nothing subscribed yet");
+
+            transport.subscribe("ping");
+            transport.publish("ping", &[7]);
+            assert_eq!(transport.poll(), vec![("ping".to_string(), vec![7])]);
+            assert!(transport.poll().is_empty(), "poll should drain what it just returned");
+        }
+
+        #[test]
+        fn subscribe_remote_and_poll_transport_round_trips_a_topic_event() {
+            let transport = Rc::new(RefCell::new(LoopbackTransport::new(Box::new(IdentityCodec))));
+
+            let mut sender = ConcreteMediator::new();
+            sender.set_transport(Box::new(transport.clone()));
+
+            let mut receiver = ConcreteMediator::new();
+            receiver.set_transport(Box::new(transport.clone()));
+            receiver.subscribe_remote::<PingEvent>(TypeId::of::<PingEvent>());
+            let received = Rc::new(RefCell::new(vec![]));
+            receiver.mediate(Box::new(PingHandler { received: received.clone() }));
+
+            sender.broadcast_remote(TypeId::of::<PingEvent>(), PingEvent { count: 42 });
+            receiver.poll_transport();
+
+            assert_eq!(*received.borrow(), vec![42]);
+        }
+    }
+}
+
+pub mod reactive {
+    // Derived values layered on top of the observer subsystem: a `Signal<T>` is a `Subject<T>`
+    // holding a value, and a `Computed<T>` lazily re-evaluates a closure whenever a signal it
+    // read last time has since changed, automatically discovering which signals it depends on.
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::{ Rc, Weak };
+    use crate::observer::{ Observer, Subject, Trigger, TriggerEvent };
+
+    // Implementations hold only a Weak reference to the Computed they invalidate: that Computed
+    // already owns a strong Rc to every subject it reads (through its closure and its detachers),
+    // so a strong Rc the other way here would form an unbreakable Computed <-> Signal/Computed cycle.
+    trait Invalidate {
+        fn invalidate(&self);
+        fn is_alive(&self) -> bool;
+    }
+
+    struct TrackingContext {
+        invalidate: Rc<dyn Invalidate>,
+        detachers: Vec<Box<dyn Fn()>>
+    }
+
+    thread_local! {
+        // The `Computed`s currently being (re)evaluated, innermost last. Reading a `Signal` or
+        // `Computed` while one of these is on top registers it as a dependency of that entry.
+        static EVALUATING_STACK: RefCell<Vec<Rc<RefCell<TrackingContext>>>> = const { RefCell::new(vec![]) };
+    }
+
+    struct DirtyObserver {
+        invalidate: Rc<dyn Invalidate>
+    }
+
+    impl<T> Observer<T> for DirtyObserver {
+        fn on_trigger(&mut self, trigger: &Trigger<T>) {
+            if let TriggerEvent::OnChange = trigger.event {
+                self.invalidate.invalidate();
+            }
+        }
+
+        fn is_alive(&self) -> bool {
+            self.invalidate.is_alive()
+        }
+    }
+
+    fn track_read<S, T>(subject: &Rc<RefCell<S>>)
+    where
+        S: Subject<T> + 'static,
+        T: 'static
+    {
+        EVALUATING_STACK.with(|stack| {
+            let context = match stack.borrow().last() {
+                Some(context) => context.clone(),
+                None => return
+            };
+            let invalidate = context.borrow().invalidate.clone();
+            let observer_key = format!("computed:{:p}", Rc::as_ptr(&invalidate));
+            subject.borrow_mut().attach_observer(&observer_key, Box::new(DirtyObserver { invalidate }));
+            let subject = subject.clone();
+            let detach_key = observer_key.clone();
+            context.borrow_mut().detachers.push(Box::new(move || {
+                subject.borrow_mut().detach_observer(&detach_key);
+            }));
+        });
+    }
+
+    pub struct Signal<T> {
+        name: String,
+        value: T,
+        observer_map: HashMap<String, Box<dyn Observer<T>>>
+    }
+
+    impl<T: Clone + 'static> Signal<T> {
+        pub fn new(name: String, initial_value: T) -> Rc<RefCell<Self>> {
+            Rc::new(RefCell::new(Self {
+                name,
+                value: initial_value,
+                observer_map: HashMap::new()
+            }))
+        }
+
+        pub fn get(signal: &Rc<RefCell<Self>>) -> T {
+            track_read(signal);
+            signal.borrow().value.clone()
+        }
+
+        pub fn set(signal: &Rc<RefCell<Self>>, new_value: T) {
+            let previous_value = signal.borrow().value.clone();
+            signal.borrow_mut().value = new_value;
+            signal.borrow_mut().notify_observers(TriggerEvent::OnChange, Some(previous_value));
+        }
+    }
+
+    impl<T: Clone + 'static> Subject<T> for Signal<T> {
+        fn attach_observer(&mut self, observer_key: &String, observer: Box<dyn Observer<T>>) {
+            self.observer_map.insert(observer_key.to_string(), observer);
+        }
+
+        fn detach_observer(&mut self, observer_key: &String) -> bool {
+            self.observer_map.remove(observer_key).is_some()
+        }
+
+        fn notify_observers(&mut self, event: TriggerEvent, previous_value: Option<T>) {
+            self.observer_map.retain(|_, observer| observer.is_alive());
+            let current_value = self.value.clone();
+            for observer in self.observer_map.values_mut() {
+                observer.on_trigger(&Trigger {
+                    event,
+                    subject_name: self.name.clone(),
+                    previous_value: previous_value.clone(),
+                    current_value: Some(current_value.clone())
+                });
+            }
+        }
+    }
+
+    pub struct Computed<T> {
+        name: String,
+        cached_value: Option<T>,
+        dirty: Rc<RefCell<bool>>,
+        compute: Rc<dyn Fn() -> T>,
+        detachers: Vec<Box<dyn Fn()>>,
+        observer_map: HashMap<String, Box<dyn Observer<T>>>
+    }
+
+    impl<T: Clone + 'static> Computed<T> {
+        pub fn new(name: String, compute: impl Fn() -> T + 'static) -> Rc<RefCell<Self>> {
+            Rc::new(RefCell::new(Self {
+                name,
+                cached_value: None,
+                dirty: Rc::new(RefCell::new(true)),
+                compute: Rc::new(compute),
+                detachers: vec![],
+                observer_map: HashMap::new()
+            }))
+        }
+
+        pub fn get(computed: &Rc<RefCell<Self>>) -> T {
+            let is_dirty = *computed.borrow().dirty.borrow();
+            if is_dirty {
+                Self::recompute(computed);
+            }
+            track_read(computed);
+            computed.borrow().cached_value.clone().unwrap()
+        }
+
+        fn recompute(computed: &Rc<RefCell<Self>>) {
+            let stale_detachers = std::mem::take(&mut computed.borrow_mut().detachers);
+            for detach in stale_detachers {
+                detach();
+            }
+
+            let dirty = computed.borrow().dirty.clone();
+            let compute_fn = computed.borrow().compute.clone();
+            let invalidate: Rc<dyn Invalidate> = Rc::new(ComputedInvalidator { computed: Rc::downgrade(computed) });
+            let context = Rc::new(RefCell::new(TrackingContext { invalidate, detachers: vec![] }));
+            EVALUATING_STACK.with(|stack| stack.borrow_mut().push(context.clone()));
+            let value = compute_fn();
+            EVALUATING_STACK.with(|stack| { stack.borrow_mut().pop(); });
+            // Dependencies read above may have invalidated us again mid-recompute (e.g. a
+            // diamond where two paths both lead back here); only clear dirty now that we've
+            // settled on the value computed from the dependencies we actually read.
+            *dirty.borrow_mut() = false;
+
+            let fresh_detachers = Rc::try_unwrap(context).ok().unwrap().into_inner().detachers;
+            let mut computed_mut = computed.borrow_mut();
+            computed_mut.cached_value = Some(value);
+            computed_mut.detachers = fresh_detachers;
+        }
+    }
+
+    struct ComputedInvalidator<T> {
+        computed: Weak<RefCell<Computed<T>>>
+    }
+
+    impl<T: Clone + 'static> Invalidate for ComputedInvalidator<T> {
+        fn invalidate(&self) {
+            let Some(computed) = self.computed.upgrade() else {
+                return;
+            };
+            let dirty = computed.borrow().dirty.clone();
+            let was_dirty = std::mem::replace(&mut *dirty.borrow_mut(), true);
+            if was_dirty {
+                // Already dirty, which means this computed's own dependents were already
+                // notified the last time it was invalidated - nothing further to cascade.
+                return;
+            }
+            let previous_value = computed.borrow().cached_value.clone();
+            computed.borrow_mut().notify_observers(TriggerEvent::OnChange, previous_value);
+        }
+
+        fn is_alive(&self) -> bool {
+            self.computed.strong_count() > 0
+        }
+    }
+
+    impl<T: Clone + 'static> Subject<T> for Computed<T> {
+        fn attach_observer(&mut self, observer_key: &String, observer: Box<dyn Observer<T>>) {
+            self.observer_map.insert(observer_key.to_string(), observer);
+        }
+
+        fn detach_observer(&mut self, observer_key: &String) -> bool {
+            self.observer_map.remove(observer_key).is_some()
+        }
+
+        fn notify_observers(&mut self, event: TriggerEvent, previous_value: Option<T>) {
+            self.observer_map.retain(|_, observer| observer.is_alive());
+            let current_value = self.cached_value.clone();
+            for observer in self.observer_map.values_mut() {
+                observer.on_trigger(&Trigger {
+                    event,
+                    subject_name: self.name.clone(),
+                    previous_value: previous_value.clone(),
+                    current_value: current_value.clone()
+                });
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn computed_chain_stays_up_to_date_without_reading_the_middle_computed() {
+            let s = Signal::new("s".to_string(), 1);
+            let a = Computed::new("a".to_string(), {
+                let s = s.clone();
+                move || Signal::get(&s) + 1
+            });
+            let b = Computed::new("b".to_string(), {
+                let a = a.clone();
+                move || Computed::get(&a) * 10
+            });
+
+            assert_eq!(Computed::get(&b), 20);
+
+            Signal::set(&s, 5);
+
+            // `a` is never read directly here - `b` must still see that it's stale.
+            assert_eq!(Computed::get(&b), 60);
+        }
+
+        #[test]
+        fn diamond_dependency_settles_after_a_single_signal_change() {
+            let s = Signal::new("s".to_string(), 2);
+            let double = Computed::new("double".to_string(), {
+                let s = s.clone();
+                move || Signal::get(&s) * 2
+            });
+            let triple = Computed::new("triple".to_string(), {
+                let s = s.clone();
+                move || Signal::get(&s) * 3
+            });
+            let sum = Computed::new("sum".to_string(), {
+                let double = double.clone();
+                let triple = triple.clone();
+                move || Computed::get(&double) + Computed::get(&triple)
+            });
+
+            assert_eq!(Computed::get(&sum), 10); // 2*2 + 2*3
+
+            Signal::set(&s, 10);
+
+            assert_eq!(Computed::get(&sum), 50); // 10*2 + 10*3
+        }
+
+        #[test]
+        fn dropping_a_signal_and_its_dependent_computed_frees_both() {
+            let s = Signal::new("s".to_string(), 1);
+            let c = Computed::new("c".to_string(), {
+                let s = s.clone();
+                move || Signal::get(&s) + 1
+            });
+            assert_eq!(Computed::get(&c), 2);
+
+            let weak_s = Rc::downgrade(&s);
+            let weak_c = Rc::downgrade(&c);
+            drop(s);
+            drop(c);
+
+            assert!(weak_s.upgrade().is_none(), "Signal should be freed once its only handles are dropped");
+            assert!(weak_c.upgrade().is_none(), "Computed should be freed once its only handles are dropped");
+        }
     }
 }
 
@@ -76,9 +830,10 @@ use std::any::{ TypeId, Any };
 use std::collections::HashMap;
 use std::rc::Rc;
 use core::cell::RefCell;
-use command::Command;
-use observer::{ Observer, Subject };
-use mediator::{ Mediator, Handler, ConcreteMediator };
+use command::{ Command, CommandInvoker, MacroCommand };
+use observer::{ Observer, Subject, SyncSubject, Trigger, TriggerEvent };
+use reactive::{ Signal, Computed };
+use mediator::{ Mediator, Handler, ConcreteMediator, IdentityCodec, LoopbackTransport, TopicEvent };
 
 pub struct Light {
     name: String,
@@ -96,20 +851,22 @@ impl Light {
     }
 
     pub fn on(&mut self) {
+        let previous_state = self.state;
         self.state = true;
-        self.notify_observers();
+        self.notify_observers(TriggerEvent::OnChange, Some(previous_state));
     }
 
     pub fn off(&mut self) {
+        let previous_state = self.state;
         self.state = false;
-        self.notify_observers();
+        self.notify_observers(TriggerEvent::OnChange, Some(previous_state));
     }
 }
 
 
 /* <command pattern example> */
 pub struct LightOnCommand {
-   light: Rc<RefCell<Light>> 
+   light: Rc<RefCell<Light>>
 }
 
 impl Command for LightOnCommand {
@@ -117,10 +874,15 @@ impl Command for LightOnCommand {
         let mut light = self.light.borrow_mut();
         light.on();
     }
+
+    fn undo(&mut self) {
+        let mut light = self.light.borrow_mut();
+        light.off();
+    }
 }
 
 pub struct LightOffCommand {
-   light: Rc<RefCell<Light>> 
+   light: Rc<RefCell<Light>>
 }
 
 impl Command for LightOffCommand {
@@ -128,41 +890,81 @@ impl Command for LightOffCommand {
         let mut light = self.light.borrow_mut();
         light.off();
     }
+
+    fn undo(&mut self) {
+        let mut light = self.light.borrow_mut();
+        light.on();
+    }
 }
 
 pub struct Remote {
-    command: Box<dyn Command>
+    command: Option<Box<dyn Command>>,
+    invoker: CommandInvoker
 }
 
 impl Remote {
+    pub fn new() -> Self {
+        Self {
+            command: None,
+            invoker: CommandInvoker::new()
+        }
+    }
+
     pub fn set_command(&mut self, command: Box<dyn Command>) {
-        self.command = command
+        self.command = Some(command)
     }
 
     pub fn execute(&mut self) {
-        self.command.execute();
+        if let Some(command) = self.command.take() {
+            self.invoker.execute(command);
+        }
+    }
+
+    pub fn undo(&mut self) {
+        self.invoker.undo();
+    }
+
+    pub fn redo(&mut self) {
+        self.invoker.redo();
     }
 }
 /* </command pattern example> */
 
 /* <observer pattern example> */
 impl Subject<bool> for Light {
-    fn attach_observer(&mut self, observer_key: &String, observer: Box<dyn Observer<bool>>) {
+    fn attach_observer(&mut self, observer_key: &String, mut observer: Box<dyn Observer<bool>>) {
+        observer.on_trigger(&Trigger {
+            event: TriggerEvent::OnAttach,
+            subject_name: self.name.clone(),
+            previous_value: None,
+            current_value: Some(self.state)
+        });
         self.observer_map.insert(observer_key.to_string(), observer);
     }
 
     fn detach_observer(&mut self, observer_key: &String) -> bool {
-        if !self.observer_map.contains_key(observer_key) {
-            return false;
+        match self.observer_map.remove(observer_key) {
+            Some(mut observer) => {
+                observer.on_trigger(&Trigger {
+                    event: TriggerEvent::OnDetach,
+                    subject_name: self.name.clone(),
+                    previous_value: Some(self.state),
+                    current_value: None
+                });
+                true
+            },
+            None => false
         }
-
-        self.observer_map.remove(observer_key);
-        return true;
     }
 
-    fn notify_observers(&mut self) {
+    fn notify_observers(&mut self, event: TriggerEvent, previous_value: Option<bool>) {
         for observer in self.observer_map.values_mut() {
-            observer.on_subject_updated(&self.state);
+            observer.on_trigger(&Trigger {
+                event,
+                subject_name: self.name.clone(),
+                previous_value,
+                current_value: Some(self.state)
+            });
         }
     }
 }
@@ -172,13 +974,24 @@ pub struct LightStateObserver {
 }
 
 impl Observer<bool> for LightStateObserver {
-    fn on_subject_updated(&mut self, update_source: &bool) {
+    fn on_trigger(&mut self, trigger: &Trigger<bool>) {
+        match trigger.event {
+            TriggerEvent::OnAttach => {
+                println!("Observer attached to light '{}'", trigger.subject_name);
+                return;
+            },
+            TriggerEvent::OnDetach => {
+                println!("Observer detached from light '{}'", trigger.subject_name);
+                return;
+            },
+            TriggerEvent::OnChange => {}
+        }
         self.update_count += 1;
         let mut light_state = "off";
-        if *update_source {
+        if trigger.current_value.unwrap_or(false) {
             light_state = "on"
         }
-        println!("Light was switched {} and has been switched a total of {} time(s)", light_state, self.update_count);
+        println!("Light '{}' was switched {} and has been switched a total of {} time(s)", trigger.subject_name, light_state, self.update_count);
     }
 }
 /* </observer pattern example> */
@@ -186,7 +999,8 @@ impl Observer<bool> for LightStateObserver {
 /* <mediator pattern example> */
 pub enum LightActionType {
     On,
-    Off
+    Off,
+    Query
 }
 
 pub struct LightAction {
@@ -194,6 +1008,35 @@ pub struct LightAction {
     light_name: String
 }
 
+impl TopicEvent for LightAction {
+    fn topic() -> &'static str {
+        "light_action"
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let action_byte = match self.action_type {
+            LightActionType::On => 0u8,
+            LightActionType::Off => 1u8,
+            LightActionType::Query => 2u8
+        };
+        let mut bytes = vec![action_byte];
+        bytes.extend(self.light_name.as_bytes());
+        bytes
+    }
+
+    fn from_bytes(payload: &[u8]) -> Option<Self> {
+        let (&action_byte, light_name_bytes) = payload.split_first()?;
+        let action_type = match action_byte {
+            0 => LightActionType::On,
+            1 => LightActionType::Off,
+            2 => LightActionType::Query,
+            _ => return None
+        };
+        let light_name = String::from_utf8(light_name_bytes.to_vec()).ok()?;
+        Some(LightAction { action_type, light_name })
+    }
+}
+
 pub struct LightActionHandler {
     light_map: HashMap<String, Rc<RefCell<Light>>>
 }
@@ -218,15 +1061,15 @@ impl Handler for LightActionHandler {
                 // whole service and also use the command pattern from earlier
                 match self.light_map.get(&light_action.light_name) {
                     Some(light) => {
-                        let mut remote: Remote;
+                        let mut remote = Remote::new();
                         match light_action.action_type {
                             LightActionType::On => {
-                                remote = Remote { command: Box::new(LightOnCommand { light: light.clone() }) };
-        
+                                remote.set_command(Box::new(LightOnCommand { light: light.clone() }));
                             },
                             LightActionType::Off => {
-                                remote = Remote { command: Box::new(LightOffCommand { light: light.clone() }) };
-                            }
+                                remote.set_command(Box::new(LightOffCommand { light: light.clone() }));
+                            },
+                            LightActionType::Query => return
                         }
                         remote.execute();
                     },
@@ -237,6 +1080,21 @@ impl Handler for LightActionHandler {
         }
     }
 
+    fn handle_request(&mut self, event: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+        match event.downcast_ref::<LightAction>() {
+            Some(light_action) => {
+                match light_action.action_type {
+                    LightActionType::Query => {
+                        self.light_map.get(&light_action.light_name)
+                            .map(|light| Box::new(light.borrow().state) as Box<dyn Any>)
+                    },
+                    LightActionType::On | LightActionType::Off => None
+                }
+            },
+            None => None
+        }
+    }
+
     fn handles_type(&self) -> TypeId {
         TypeId::of::<LightAction>()
     }
@@ -262,4 +1120,98 @@ fn main() {
         action_type: LightActionType::Off
     };
     light_mediator.broadcast(TypeId::of::<LightAction>(), Box::new(turn_off_light_1_action));
+
+    // Demonstrate undo/redo and macro commands directly through the Remote.
+    let light_2 = Rc::new(RefCell::new(Light::new("light_2".to_string(), false)));
+    light_2.borrow_mut().attach_observer(
+        &"light_observer_2".to_string(),
+        Box::new(LightStateObserver { update_count: 0 })
+    );
+    let mut remote = Remote::new();
+    remote.set_command(Box::new(LightOnCommand { light: light_2.clone() }));
+    remote.execute(); // light_2 on
+    remote.undo(); // light_2 off
+    remote.redo(); // light_2 on
+    remote.set_command(Box::new(MacroCommand::new(vec![
+        Box::new(LightOffCommand { light: light_2.clone() }),
+        Box::new(LightOnCommand { light: light_2.clone() }),
+    ])));
+    remote.execute(); // light_2 off, then on
+    remote.undo(); // light_2 off, then on (reverse order)
+
+    // A closure can stand in for a one-off command struct.
+    let light_2_for_closure = light_2.clone();
+    remote.set_command(<dyn Command>::from_fn(move || light_2_for_closure.borrow_mut().on()));
+    remote.execute(); // light_2 on
+
+    // Query the light's current state through the mediator instead of just toggling it.
+    let query_light_1_action = LightAction {
+        light_name: light_name.clone(),
+        action_type: LightActionType::Query
+    };
+    let responses = light_mediator.request(TypeId::of::<LightAction>(), Box::new(query_light_1_action));
+    for response in responses {
+        if let Ok(is_on) = response.downcast::<bool>() {
+            println!("Light '{}' is currently {}", light_name, if *is_on { "on" } else { "off" });
+        }
+    }
+
+    // Demonstrate a computed value that tracks its own signal dependencies automatically.
+    let brightness = Signal::new("brightness".to_string(), 10);
+    let doubled_brightness = Computed::new("doubled_brightness".to_string(), {
+        let brightness = brightness.clone();
+        move || Signal::get(&brightness) * 2
+    });
+    println!("Doubled brightness is {}", Computed::get(&doubled_brightness));
+    Signal::set(&brightness, 40);
+    println!("Doubled brightness is {}", Computed::get(&doubled_brightness));
+
+    // Demonstrate observers being notified on a background worker thread.
+    let mut sync_light_state = SyncSubject::<bool>::new("sync_light".to_string());
+    println!("Starting background notifications for subject '{}'", sync_light_state.name());
+    sync_light_state.attach_observer(
+        &"sync_light_observer".to_string(),
+        Box::new(LightStateObserver { update_count: 0 })
+    );
+    sync_light_state.notify_async(Some(false), true);
+    sync_light_state.notify_async(Some(true), false);
+    sync_light_state.shutdown(); // blocks until the worker has drained both notifications
+
+    // Demonstrate bridging a mediator's events over a transport to a second, independent
+    // mediator - as if the two were running in separate processes.
+    let mut local_light = Light::new("light_3".to_string(), false);
+    local_light.attach_observer(&"light_3_local_observer".to_string(), Box::new(LightStateObserver { update_count: 0 }));
+    let mut local_handler = LightActionHandler::new();
+    local_handler.add_light(local_light);
+    let mut local_mediator = ConcreteMediator::new();
+    local_mediator.mediate(Box::new(local_handler));
+    let transport = Rc::new(RefCell::new(LoopbackTransport::new(Box::new(IdentityCodec))));
+    local_mediator.set_transport(Box::new(transport.clone()));
+
+    let mut remote_light = Light::new("light_3".to_string(), false);
+    remote_light.attach_observer(&"light_3_remote_observer".to_string(), Box::new(LightStateObserver { update_count: 0 }));
+    let mut remote_handler = LightActionHandler::new();
+    remote_handler.add_light(remote_light);
+    let mut remote_mediator = ConcreteMediator::new();
+    remote_mediator.mediate(Box::new(remote_handler));
+
+    let turn_on_light_3_action = LightAction {
+        light_name: "light_3".to_string(),
+        action_type: LightActionType::On
+    };
+    local_mediator.broadcast_remote(TypeId::of::<LightAction>(), turn_on_light_3_action);
+    for payload in transport.borrow_mut().drain("light_action") {
+        remote_mediator.receive::<LightAction>(TypeId::of::<LightAction>(), &payload);
+    }
+
+    // Subscribing drives the same bridge without a manual drain/receive: the remote mediator
+    // polls the transport for whatever arrived on topics it subscribed to.
+    remote_mediator.set_transport(Box::new(transport.clone()));
+    remote_mediator.subscribe_remote::<LightAction>(TypeId::of::<LightAction>());
+    let turn_off_light_3_action = LightAction {
+        light_name: "light_3".to_string(),
+        action_type: LightActionType::Off
+    };
+    local_mediator.broadcast_remote(TypeId::of::<LightAction>(), turn_off_light_3_action);
+    remote_mediator.poll_transport();
 }